@@ -0,0 +1,5 @@
+pub mod msg;
+pub mod query;
+pub mod rewards;
+pub mod staking;
+pub mod state;
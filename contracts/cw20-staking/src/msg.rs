@@ -0,0 +1,31 @@
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use oraiswap::asset::AssetInfo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockInfo {
+    pub amount: Uint128,
+    pub unlock_time: Timestamp,
+}
+
+/// A staker's full position in a pool: active bond, in-flight unbonding split by whether it
+/// has already matured, and the sum of the three.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakedInfoResponse {
+    pub bond_amount: Uint128,
+    pub pending_unbond_amount: Uint128,
+    pub claimable_amount: Uint128,
+    pub total_amount: Uint128,
+}
+
+/// One entry of the staking-token whitelist, as returned by the enumeration query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WhitelistInfoResponse {
+    pub staking_token: Addr,
+    pub reward_asset: AssetInfo,
+    pub reward_per_sec: Uint128,
+    pub unbonding_period: Option<u64>,
+    pub min_bond: Uint128,
+    pub max_stakers: Option<u32>,
+}
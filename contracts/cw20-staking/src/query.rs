@@ -0,0 +1,54 @@
+use crate::msg::{StakedInfoResponse, WhitelistInfoResponse};
+use crate::state::{range_whitelist, read_lock_summary, STAKED_BALANCES};
+use cosmwasm_std::{Addr, CanonicalAddr, Deps, Env, StdResult};
+
+/// Active bond plus pending and claimable unbond amounts, and their sum, in one call instead of
+/// combining `STAKED_BALANCES` with the lock buckets separately.
+pub fn query_staked_info(
+    deps: Deps,
+    env: Env,
+    staker: Addr,
+    staking_token: Addr,
+) -> StdResult<StakedInfoResponse> {
+    let asset_key = deps.api.addr_canonicalize(staking_token.as_str())?.to_vec();
+
+    let bond_amount = STAKED_BALANCES
+        .may_load(deps.storage, (&asset_key, &staker))?
+        .unwrap_or_default();
+
+    let (pending_unbond_amount, claimable_amount) = read_lock_summary(
+        deps.storage,
+        staking_token.as_bytes(),
+        staker.as_bytes(),
+        env.block.time,
+    )?;
+
+    let total_amount = bond_amount
+        .checked_add(pending_unbond_amount)?
+        .checked_add(claimable_amount)?;
+
+    Ok(StakedInfoResponse {
+        bond_amount,
+        pending_unbond_amount,
+        claimable_amount,
+        total_amount,
+    })
+}
+
+/// Enumerates every whitelisted staking token and its reward configuration.
+pub fn query_whitelist(deps: Deps) -> StdResult<Vec<WhitelistInfoResponse>> {
+    range_whitelist(deps.storage)
+        .map(|item| {
+            let (asset_key, entry) = item?;
+            let staking_token = deps.api.addr_humanize(&CanonicalAddr::from(asset_key))?;
+            Ok(WhitelistInfoResponse {
+                staking_token,
+                reward_asset: entry.reward_asset,
+                reward_per_sec: entry.reward_per_sec,
+                unbonding_period: entry.unbonding_period,
+                min_bond: entry.min_bond,
+                max_stakers: entry.max_stakers,
+            })
+        })
+        .collect()
+}
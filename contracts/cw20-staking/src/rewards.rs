@@ -0,0 +1,14 @@
+use crate::state::RewardInfo;
+use cosmwasm_std::{Decimal, StdResult};
+
+/// Settles the staker's share of rewards accrued since their last checkpoint into
+/// `pending_reward`, using the pool's current reward index. Must be called before any
+/// change to `bond_amount` so earlier rewards are priced at the old share.
+pub fn before_share_change(pool_index: Decimal, reward_info: &mut RewardInfo) -> StdResult<()> {
+    let pending_reward = (reward_info.bond_amount * pool_index)
+        .checked_sub(reward_info.bond_amount * reward_info.index)?;
+
+    reward_info.index = pool_index;
+    reward_info.pending_reward += pending_reward;
+    Ok(())
+}
@@ -1,13 +1,14 @@
 use crate::msg::LockInfo;
 use crate::rewards::before_share_change;
 use crate::state::{
-    insert_lock_info, read_pool_info, read_unbonding_period, remove_and_accumulate_lock_info,
-    rewards_read, rewards_store, stakers_store, store_pool_info, PoolInfo, RewardInfo,
+    insert_lock_info, read_pool_info, read_unbonding_period, read_whitelist,
+    remove_and_accumulate_lock_info, remove_whitelist, rewards_read, rewards_store, stakers_store,
+    store_pool_info, store_whitelist, PoolInfo, RewardInfo, WhitelistEntry, CONFIG,
     STAKED_BALANCES, STAKED_TOTAL,
 };
 use cosmwasm_std::{
-    attr, to_binary, Addr, Api, CanonicalAddr, CosmosMsg, Decimal, DepsMut, Env, Response,
-    StdError, StdResult, Storage, Uint128, WasmMsg,
+    attr, to_binary, Addr, Api, CanonicalAddr, CosmosMsg, Decimal, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 use oraiswap::asset::{self, Asset};
@@ -19,17 +20,26 @@ pub fn bond(
     staking_token: Addr,
     amount: Uint128,
 ) -> StdResult<Response> {
+    let asset_key = deps.api.addr_canonicalize(staking_token.as_str())?.to_vec();
+    if read_whitelist(deps.storage, &asset_key)?.is_none() {
+        return Err(StdError::generic_err(format!(
+            "AssetNotWhitelisted: {} is not a whitelisted staking token",
+            staking_token
+        )));
+    }
+
     let staker_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(staker_addr.as_str())?;
-    _increase_bond_amount(
+    let mint_messages = _increase_bond_amount(
         deps.storage,
         deps.api,
         env.block.height,
+        env.block.time,
         &staker_addr_raw,
         staking_token.clone(),
         amount,
     )?;
 
-    Ok(Response::new().add_attributes([
+    Ok(Response::new().add_messages(mint_messages).add_attributes([
         ("action", "bond"),
         ("staker_addr", staker_addr.as_str()),
         ("staking_token", staking_token.as_str()),
@@ -63,6 +73,16 @@ pub fn unbond(
 
     let withdraw_attrs = withdraw_response.attributes;
     if !amount.is_zero() {
+        if read_pool_info(deps.storage, asset_key.as_slice())?
+            .derivative_token
+            .is_some()
+        {
+            return Err(StdError::generic_err(
+                "LiquidPool: bond ownership is represented by the receipt token; \
+                 call redeem_liquid_stake instead of unbond to withdraw the underlying",
+            ));
+        }
+
         let (_, reward_assets) = _decrease_bond_amount(
             deps.storage,
             deps.api,
@@ -85,6 +105,7 @@ pub fn unbond(
                 deps.storage,
                 staking_token.as_bytes(),
                 staker_addr.as_bytes(),
+                period,
                 LockInfo {
                     amount,
                     unlock_time,
@@ -115,13 +136,25 @@ pub fn unbond(
         .add_attributes(withdraw_attrs))
 }
 
+/// Permissionless: anyone may trigger this for `staker_addr` to clear their matured lock
+/// buckets. Tokens go to `staker_addr`, never the caller.
+pub fn withdraw_unbonded(
+    deps: DepsMut,
+    env: Env,
+    staker_addr: Addr,
+    staking_token: Addr,
+) -> StdResult<Response> {
+    _withdraw_lock(deps.storage, &env, &staker_addr, &staking_token)
+}
+
 pub fn _withdraw_lock(
     storage: &mut dyn Storage,
     env: &Env,
     staker_addr: &Addr,
     staking_token: &Addr,
 ) -> StdResult<Response> {
-    // execute 10 lock a time
+    // sweep matured era buckets (up to MAX_BUCKETS_PER_SWEEP) and unbond the folded total in
+    // one transfer
     let unlock_amount = remove_and_accumulate_lock_info(
         storage,
         staking_token.as_bytes(),
@@ -142,12 +175,43 @@ fn _increase_bond_amount(
     storage: &mut dyn Storage,
     api: &dyn Api,
     height: u64,
+    block_time: Timestamp,
     staker_addr: &CanonicalAddr,
     staking_token: Addr,
     amount: Uint128,
-) -> StdResult<()> {
+) -> StdResult<Vec<CosmosMsg>> {
     let asset_key = api.addr_canonicalize(staking_token.as_str())?.to_vec();
     let mut pool_info = read_pool_info(storage, &asset_key)?;
+
+    if let Some(derivative_token) = pool_info.derivative_token.clone() {
+        // Liquid-staking pools: the transferable receipt token *is* the position, so there is
+        // no per-staker RewardInfo to pay into. min_bond/max_stakers likewise don't apply here
+        // since there's no per-staker storage to gate — those only constrain the non-liquid
+        // path below. Rewards instead compound directly into the exchange rate.
+        _accrue_liquid_reward(&mut pool_info, block_time);
+        let receipt_amount = _exchange_amount(
+            amount,
+            pool_info.total_receipt_supply,
+            pool_info.total_bond_amount,
+        );
+        pool_info.total_bond_amount += amount;
+        pool_info.total_receipt_supply += receipt_amount;
+        store_pool_info(storage, &asset_key, &pool_info)?;
+
+        STAKED_TOTAL.update(storage, &asset_key, height, |total| -> StdResult<Uint128> {
+            Ok(total.unwrap_or_default().checked_add(amount)?)
+        })?;
+
+        return Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: api.addr_humanize(&derivative_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: api.addr_humanize(staker_addr)?.to_string(),
+                amount: receipt_amount,
+            })?,
+            funds: vec![],
+        })]);
+    }
+
     let mut reward_info: RewardInfo = rewards_read(storage, staker_addr)
         .load(&asset_key)
         .unwrap_or_else(|_| RewardInfo {
@@ -166,6 +230,30 @@ fn _increase_bond_amount(
 
     reward_info.bond_amount += amount;
 
+    // Enforce the pool's minimum position size against the resulting bond, not the incoming
+    // amount, so a dust top-up to an already-qualifying position is still allowed.
+    if reward_info.bond_amount < pool_info.min_bond {
+        return Err(StdError::generic_err(format!(
+            "BondTooSmall: bond_amount {} is below the pool's min_bond {}",
+            reward_info.bond_amount, pool_info.min_bond
+        )));
+    }
+
+    let is_new_staker = stakers_store(storage, &asset_key)
+        .may_load(staker_addr)?
+        .is_none();
+    if is_new_staker {
+        if let Some(max_stakers) = pool_info.max_stakers {
+            if pool_info.staker_count >= max_stakers {
+                return Err(StdError::generic_err(format!(
+                    "TooManyStakers: pool already has the maximum {} stakers",
+                    max_stakers
+                )));
+            }
+        }
+        pool_info.staker_count += 1;
+    }
+
     STAKED_BALANCES.update(
         storage,
         (&asset_key, &api.addr_humanize(staker_addr)?),
@@ -183,12 +271,40 @@ fn _increase_bond_amount(
     store_pool_info(storage, &asset_key, &pool_info)?;
 
     // mark this staker belong to the pool the first time
-    let mut stakers_bucket = stakers_store(storage, &asset_key);
-    if stakers_bucket.may_load(staker_addr)?.is_none() {
-        stakers_bucket.save(staker_addr, &true)?;
+    if is_new_staker {
+        stakers_store(storage, &asset_key).save(staker_addr, &true)?;
     }
 
-    Ok(())
+    Ok(vec![])
+}
+
+/// Compounds a liquid pool's configured `reward_per_sec` into `total_bond_amount` for the time
+/// elapsed since `last_reward_time`. This is how liquid stakers earn rewards: since there is no
+/// per-staker `RewardInfo` to pay into, the reward instead raises the underlying backing every
+/// receipt token redeems for. No-op once caught up to `block_time`.
+fn _accrue_liquid_reward(pool_info: &mut PoolInfo, block_time: Timestamp) {
+    let now = block_time.seconds();
+    let elapsed = now.saturating_sub(pool_info.last_reward_time);
+    if elapsed > 0 {
+        pool_info.total_bond_amount += pool_info.reward_per_sec * Uint128::from(elapsed);
+        pool_info.last_reward_time = now;
+    }
+}
+
+/// Converts `amount` of one side of the bond/receipt exchange into the other: pass
+/// `(bond_amount, total_receipt_supply, total_bond_amount)` to price an underlying deposit in
+/// receipt tokens, or `(receipt_amount, total_bond_amount, total_receipt_supply)` to price a
+/// receipt redemption in underlying. The first movement into an empty pool is always 1:1.
+fn _exchange_amount(
+    amount: Uint128,
+    numerator_total: Uint128,
+    denominator_total: Uint128,
+) -> Uint128 {
+    if numerator_total.is_zero() || denominator_total.is_zero() {
+        amount
+    } else {
+        amount.multiply_ratio(numerator_total, denominator_total)
+    }
 }
 
 fn _decrease_bond_amount(
@@ -242,12 +358,104 @@ fn _decrease_bond_amount(
     }
     rewards_store(storage, staker_addr).save(&asset_key, &reward_info)?;
 
+    // A staker who has fully unbonded no longer occupies a slot against `max_stakers` — prune
+    // them so repeated bond/unbond-to-zero can't permanently fill the pool with ghost entries.
+    if reward_info.bond_amount.is_zero() {
+        let mut stakers_bucket = stakers_store(storage, &asset_key);
+        if stakers_bucket.may_load(staker_addr)?.is_some() {
+            stakers_bucket.remove(staker_addr);
+            pool_info.staker_count = pool_info.staker_count.saturating_sub(1);
+        }
+    }
+
     // Update pool info
     store_pool_info(storage, &asset_key, &pool_info)?;
 
     Ok((staking_token, reward_assets))
 }
 
+/// Redeems a liquid pool's receipt token for the underlying, keyed on `receipt_amount` rather
+/// than `staker_addr`/`RewardInfo` so any holder can redeem, not just the original bonder. Must
+/// be called (e.g. from the receipt token's `Cw20ReceiveMsg` hook) only once the contract
+/// already holds `receipt_amount`, mirroring `bond`'s staking-token hook; it's then burned via
+/// plain `Burn`, not `BurnFrom`.
+pub fn redeem_liquid_stake(
+    deps: DepsMut,
+    env: Env,
+    redeemer_addr: Addr,
+    staking_token: Addr,
+    receipt_amount: Uint128,
+) -> StdResult<Response> {
+    let asset_key = deps.api.addr_canonicalize(staking_token.as_str())?.to_vec();
+    let mut pool_info = read_pool_info(deps.storage, &asset_key)?;
+    let derivative_token = pool_info.derivative_token.clone().ok_or_else(|| {
+        StdError::generic_err("NotLiquidPool: staking token has no receipt token to redeem")
+    })?;
+
+    _accrue_liquid_reward(&mut pool_info, env.block.time);
+    let underlying_amount = _exchange_amount(
+        receipt_amount,
+        pool_info.total_bond_amount,
+        pool_info.total_receipt_supply,
+    );
+
+    pool_info.total_receipt_supply = pool_info.total_receipt_supply.checked_sub(receipt_amount)?;
+    pool_info.total_bond_amount = pool_info.total_bond_amount.checked_sub(underlying_amount)?;
+    store_pool_info(deps.storage, &asset_key, &pool_info)?;
+
+    STAKED_TOTAL.update(
+        deps.storage,
+        &asset_key,
+        env.block.height,
+        |total| -> StdResult<Uint128> {
+            Ok(total.unwrap_or_default().checked_sub(underlying_amount)?)
+        },
+    )?;
+
+    let mut messages = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: deps.api.addr_humanize(&derivative_token)?.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Burn {
+            amount: receipt_amount,
+        })?,
+        funds: vec![],
+    })];
+
+    let mut response = Response::new();
+    if let Ok(period) = read_unbonding_period(deps.storage, &asset_key) {
+        let unlock_time = env.block.time.plus_seconds(period);
+        insert_lock_info(
+            deps.storage,
+            staking_token.as_bytes(),
+            redeemer_addr.as_bytes(),
+            period,
+            LockInfo {
+                amount: underlying_amount,
+                unlock_time,
+            },
+        )?;
+
+        response = response.add_attributes([
+            attr("action", "unbonding"),
+            attr("staker_addr", redeemer_addr.as_str()),
+            attr("amount", underlying_amount.to_string()),
+            attr("staking_token", staking_token.as_str()),
+            attr("unlock_time", unlock_time.seconds().to_string()),
+        ]);
+    } else {
+        let unbond_response = _unbond(&redeemer_addr, &staking_token, underlying_amount)?;
+        messages.extend(unbond_response.messages.into_iter().map(|msg| msg.msg));
+        response = response.add_attributes(unbond_response.attributes);
+    }
+
+    Ok(response.add_messages(messages).add_attributes([
+        attr("action", "redeem_liquid_stake"),
+        attr("redeemer_addr", redeemer_addr.as_str()),
+        attr("staking_token", staking_token.as_str()),
+        attr("receipt_amount", receipt_amount.to_string()),
+        attr("underlying_amount", underlying_amount.to_string()),
+    ]))
+}
+
 fn _unbond(staker_addr: &Addr, staking_token_addr: &Addr, amount: Uint128) -> StdResult<Response> {
     let messages: Vec<CosmosMsg> = vec![WasmMsg::Execute {
         contract_addr: staking_token_addr.to_string(),
@@ -266,3 +474,132 @@ fn _unbond(staker_addr: &Addr, staking_token_addr: &Addr, amount: Uint128) -> St
         attr("staking_token", staking_token_addr.as_str()),
     ]))
 }
+
+fn assert_owner(deps: &DepsMut, info: &MessageInfo) -> StdResult<()> {
+    let config = CONFIG.load(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err(
+            "unauthorized: sender is not the contract owner",
+        ));
+    }
+    Ok(())
+}
+
+/// Admin-only: adds or updates a staking token's whitelist entry and applies its reward/unbonding
+/// config onto the pool's `PoolInfo`. Updates in place, so an existing pool's `total_bond_amount`
+/// and reward accounting survive a reward-rate change.
+pub fn add_whitelist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staking_token: Addr,
+    entry: WhitelistEntry,
+) -> StdResult<Response> {
+    assert_owner(&deps, &info)?;
+    let asset_key = deps.api.addr_canonicalize(staking_token.as_str())?.to_vec();
+
+    let mut pool_info = read_pool_info(deps.storage, &asset_key).unwrap_or_else(|_| PoolInfo {
+        staking_token: CanonicalAddr::from(asset_key.clone()),
+        total_bond_amount: Uint128::zero(),
+        reward_index: Decimal::zero(),
+        pending_reward: Uint128::zero(),
+        reward_asset: entry.reward_asset.clone(),
+        reward_per_sec: Uint128::zero(),
+        unbonding_period: None,
+        min_bond: Uint128::zero(),
+        max_stakers: None,
+        staker_count: 0,
+        derivative_token: None,
+        total_receipt_supply: Uint128::zero(),
+        last_reward_time: env.block.time.seconds(),
+    });
+    // Compound any reward accrued at the old rate before the new rate takes effect.
+    if pool_info.derivative_token.is_some() {
+        _accrue_liquid_reward(&mut pool_info, env.block.time);
+    }
+    pool_info.reward_asset = entry.reward_asset.clone();
+    pool_info.reward_per_sec = entry.reward_per_sec;
+    pool_info.unbonding_period = entry.unbonding_period;
+    pool_info.min_bond = entry.min_bond;
+    pool_info.max_stakers = entry.max_stakers;
+    store_pool_info(deps.storage, &asset_key, &pool_info)?;
+
+    store_whitelist(deps.storage, &asset_key, &entry)?;
+
+    Ok(Response::new().add_attributes([
+        attr("action", "add_whitelist"),
+        attr("staking_token", staking_token.as_str()),
+    ]))
+}
+
+/// Admin-only: removes a staking token's whitelist entry. Existing stakers already bonded keep
+/// their position; only new `bond` calls are rejected afterwards.
+pub fn remove_from_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking_token: Addr,
+) -> StdResult<Response> {
+    assert_owner(&deps, &info)?;
+    let asset_key = deps.api.addr_canonicalize(staking_token.as_str())?.to_vec();
+    remove_whitelist(deps.storage, &asset_key);
+
+    Ok(Response::new().add_attributes([
+        attr("action", "remove_whitelist"),
+        attr("staking_token", staking_token.as_str()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exchange_amount_is_1_to_1_on_an_empty_pool() {
+        assert_eq!(
+            _exchange_amount(Uint128::new(100), Uint128::zero(), Uint128::zero()),
+            Uint128::new(100)
+        );
+    }
+
+    #[test]
+    fn exchange_amount_follows_the_live_ratio() {
+        // 200 receipt tokens outstanding against 100 bonded: each bonded token is worth 2 receipt.
+        let receipt_amount =
+            _exchange_amount(Uint128::new(50), Uint128::new(200), Uint128::new(100));
+        assert_eq!(receipt_amount, Uint128::new(100));
+
+        // and the inverse: 100 receipt redeems for 50 of the underlying at the same ratio.
+        let underlying_amount =
+            _exchange_amount(Uint128::new(100), Uint128::new(100), Uint128::new(200));
+        assert_eq!(underlying_amount, Uint128::new(50));
+    }
+
+    #[test]
+    fn accrue_liquid_reward_compounds_elapsed_seconds_once() {
+        let mut pool_info = PoolInfo {
+            staking_token: CanonicalAddr::from(vec![0u8; 3]),
+            total_bond_amount: Uint128::new(100),
+            reward_index: Decimal::zero(),
+            pending_reward: Uint128::zero(),
+            reward_asset: asset::AssetInfo::NativeToken {
+                denom: "orai".to_string(),
+            },
+            reward_per_sec: Uint128::new(2),
+            unbonding_period: None,
+            min_bond: Uint128::zero(),
+            max_stakers: None,
+            staker_count: 0,
+            derivative_token: Some(CanonicalAddr::from(vec![1u8; 3])),
+            total_receipt_supply: Uint128::new(100),
+            last_reward_time: 0,
+        };
+
+        _accrue_liquid_reward(&mut pool_info, Timestamp::from_seconds(10));
+        assert_eq!(pool_info.total_bond_amount, Uint128::new(120));
+        assert_eq!(pool_info.last_reward_time, 10);
+
+        // calling again at the same timestamp is a no-op
+        _accrue_liquid_reward(&mut pool_info, Timestamp::from_seconds(10));
+        assert_eq!(pool_info.total_bond_amount, Uint128::new(120));
+    }
+}
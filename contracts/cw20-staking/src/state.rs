@@ -0,0 +1,366 @@
+use crate::msg::LockInfo;
+use cosmwasm_std::{Addr, CanonicalAddr, Decimal, Order, StdResult, Storage, Timestamp, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, Bucket, ReadonlyBucket};
+use cw_storage_plus::{Item, SnapshotMap};
+use oraiswap::asset::{AssetInfo, AssetRaw};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+static PREFIX_POOL_INFO: &[u8] = b"pool_info";
+static PREFIX_REWARD: &[u8] = b"reward";
+static PREFIX_STAKER: &[u8] = b"staker";
+static PREFIX_LOCK_INFO: &[u8] = b"lock_info";
+static PREFIX_WHITELIST: &[u8] = b"whitelist";
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: CanonicalAddr,
+}
+
+/// Admin-managed whitelist entry for a staking token. `add_whitelist` both gates `bond` on this
+/// entry existing and copies these fields onto the pool's `PoolInfo`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WhitelistEntry {
+    pub reward_asset: AssetInfo,
+    pub reward_per_sec: Uint128,
+    pub unbonding_period: Option<u64>,
+    pub min_bond: Uint128,
+    pub max_stakers: Option<u32>,
+}
+
+/// Cap on matured buckets swept per withdraw call. Extras are left for a later call.
+const MAX_BUCKETS_PER_SWEEP: usize = 20;
+
+pub const STAKED_BALANCES: SnapshotMap<(&[u8], &Addr), Uint128> = SnapshotMap::new(
+    "staked_balances",
+    "staked_balances__checkpoints",
+    "staked_balances__changelog",
+    cw_storage_plus::Strategy::EveryBlock,
+);
+
+pub const STAKED_TOTAL: SnapshotMap<&[u8], Uint128> = SnapshotMap::new(
+    "staked_total",
+    "staked_total__checkpoints",
+    "staked_total__changelog",
+    cw_storage_plus::Strategy::EveryBlock,
+);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    pub staking_token: CanonicalAddr,
+    pub total_bond_amount: Uint128,
+    pub reward_index: Decimal,
+    pub pending_reward: Uint128,
+    pub reward_asset: AssetInfo,
+    pub reward_per_sec: Uint128,
+    pub unbonding_period: Option<u64>,
+    /// Minimum resulting `bond_amount` a `bond` must leave a staker with.
+    pub min_bond: Uint128,
+    /// Cap on distinct `stakers_store` entries. `None` means unbounded.
+    pub max_stakers: Option<u32>,
+    /// Count of current `stakers_store` entries, kept incremental to avoid scanning the bucket.
+    pub staker_count: u32,
+    /// CW20 minted by `bond` and burned by `redeem_liquid_stake` as a transferable receipt when
+    /// set; `unbond` is rejected for such a pool. `None` for a regular (non-liquid) pool.
+    pub derivative_token: Option<CanonicalAddr>,
+    /// Outstanding supply of `derivative_token`; this contract is the sole minter/burner.
+    pub total_receipt_supply: Uint128,
+    /// Last time `reward_per_sec` was compounded into `total_bond_amount`. Unused outside
+    /// liquid pools.
+    pub last_reward_time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardInfo {
+    pub native_token: bool,
+    pub index: Decimal,
+    pub bond_amount: Uint128,
+    pub pending_reward: Uint128,
+    pub pending_withdraw: Vec<AssetRaw>,
+}
+
+pub fn store_pool_info(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    pool_info: &PoolInfo,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_POOL_INFO).save(asset_key, pool_info)
+}
+
+pub fn read_pool_info(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<PoolInfo> {
+    bucket_read(storage, PREFIX_POOL_INFO).load(asset_key)
+}
+
+pub fn read_unbonding_period(storage: &dyn Storage, asset_key: &[u8]) -> StdResult<u64> {
+    read_pool_info(storage, asset_key)?
+        .unbonding_period
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("no unbonding period configured"))
+}
+
+pub fn rewards_store<'a>(
+    storage: &'a mut dyn Storage,
+    staker_addr: &CanonicalAddr,
+) -> Bucket<'a, RewardInfo> {
+    Bucket::multilevel(storage, &[PREFIX_REWARD, staker_addr.as_slice()])
+}
+
+pub fn rewards_read<'a>(
+    storage: &'a dyn Storage,
+    staker_addr: &CanonicalAddr,
+) -> ReadonlyBucket<'a, RewardInfo> {
+    ReadonlyBucket::multilevel(storage, &[PREFIX_REWARD, staker_addr.as_slice()])
+}
+
+pub fn stakers_store<'a>(storage: &'a mut dyn Storage, asset_key: &[u8]) -> Bucket<'a, bool> {
+    Bucket::multilevel(storage, &[PREFIX_STAKER, asset_key])
+}
+
+fn locks_bucket<'a>(
+    storage: &'a mut dyn Storage,
+    staking_token: &[u8],
+    staker_addr: &[u8],
+) -> Bucket<'a, Uint128> {
+    Bucket::multilevel(storage, &[PREFIX_LOCK_INFO, staking_token, staker_addr])
+}
+
+/// Rounds `unlock_time` up to the next multiple of `bucket_seconds`, the window every unbond
+/// landing in the same era is pooled under.
+fn era_bucket(unlock_time: Timestamp, bucket_seconds: u64) -> u64 {
+    let seconds = unlock_time.seconds();
+    if bucket_seconds == 0 {
+        return seconds;
+    }
+    let remainder = seconds % bucket_seconds;
+    if remainder == 0 {
+        seconds
+    } else {
+        seconds + (bucket_seconds - remainder)
+    }
+}
+
+/// Adds to the era bucket `unlock_time` quantizes into, merging with any amount already due in
+/// that window instead of appending a new entry.
+pub fn insert_lock_info(
+    storage: &mut dyn Storage,
+    staking_token: &[u8],
+    staker_addr: &[u8],
+    bucket_seconds: u64,
+    lock_info: LockInfo,
+) -> StdResult<()> {
+    let bucket_key = era_bucket(lock_info.unlock_time, bucket_seconds).to_be_bytes();
+    let mut bucket = locks_bucket(storage, staking_token, staker_addr);
+    let amount = bucket.may_load(&bucket_key)?.unwrap_or_default() + lock_info.amount;
+    bucket.save(&bucket_key, &amount)
+}
+
+/// Sums and removes every era bucket due by `block_time`, up to [`MAX_BUCKETS_PER_SWEEP`].
+/// Unmatured buckets, and any matured overflow past the cap, are left for a later call.
+pub fn remove_and_accumulate_lock_info(
+    storage: &mut dyn Storage,
+    staking_token: &[u8],
+    staker_addr: &[u8],
+    block_time: Timestamp,
+) -> StdResult<Uint128> {
+    let bucket = locks_bucket(storage, staking_token, staker_addr);
+    let due: Vec<(Vec<u8>, Uint128)> = bucket
+        .range(None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .take_while(|(key, _)| decode_bucket_key(key) <= block_time.seconds())
+        .take(MAX_BUCKETS_PER_SWEEP)
+        .collect();
+
+    let mut bucket = locks_bucket(storage, staking_token, staker_addr);
+    let mut unlock_amount = Uint128::zero();
+    for (key, amount) in &due {
+        unlock_amount += *amount;
+        bucket.remove(key);
+    }
+
+    Ok(unlock_amount)
+}
+
+fn decode_bucket_key(key: &[u8]) -> u64 {
+    u64::from_be_bytes(key.try_into().expect("bucket key is always 8 bytes"))
+}
+
+/// Read-only split of a staker's lock buckets into `(pending, claimable)` as of `block_time`,
+/// without mutating storage.
+pub fn read_lock_summary(
+    storage: &dyn Storage,
+    staking_token: &[u8],
+    staker_addr: &[u8],
+    block_time: Timestamp,
+) -> StdResult<(Uint128, Uint128)> {
+    let bucket: ReadonlyBucket<Uint128> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_LOCK_INFO, staking_token, staker_addr]);
+
+    let mut pending = Uint128::zero();
+    let mut claimable = Uint128::zero();
+    for item in bucket.range(None, None, Order::Ascending) {
+        let (key, amount) = item?;
+        if decode_bucket_key(&key) <= block_time.seconds() {
+            claimable += amount;
+        } else {
+            pending += amount;
+        }
+    }
+
+    Ok((pending, claimable))
+}
+
+pub fn range_pool_info<'a>(
+    storage: &'a dyn Storage,
+) -> Box<dyn Iterator<Item = StdResult<(Vec<u8>, PoolInfo)>> + 'a> {
+    bucket_read(storage, PREFIX_POOL_INFO).range(None, None, Order::Ascending)
+}
+
+pub fn store_whitelist(
+    storage: &mut dyn Storage,
+    asset_key: &[u8],
+    entry: &WhitelistEntry,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_WHITELIST).save(asset_key, entry)
+}
+
+pub fn read_whitelist(
+    storage: &dyn Storage,
+    asset_key: &[u8],
+) -> StdResult<Option<WhitelistEntry>> {
+    bucket_read(storage, PREFIX_WHITELIST).may_load(asset_key)
+}
+
+pub fn remove_whitelist(storage: &mut dyn Storage, asset_key: &[u8]) {
+    Bucket::<WhitelistEntry>::new(storage, PREFIX_WHITELIST).remove(asset_key)
+}
+
+pub fn range_whitelist<'a>(
+    storage: &'a dyn Storage,
+) -> Box<dyn Iterator<Item = StdResult<(Vec<u8>, WhitelistEntry)>> + 'a> {
+    bucket_read(storage, PREFIX_WHITELIST).range(None, None, Order::Ascending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn era_bucket_rounds_up_to_next_window() {
+        assert_eq!(era_bucket(Timestamp::from_seconds(100), 0), 100);
+        assert_eq!(era_bucket(Timestamp::from_seconds(100), 50), 100);
+        assert_eq!(era_bucket(Timestamp::from_seconds(101), 50), 150);
+        assert_eq!(era_bucket(Timestamp::from_seconds(149), 50), 150);
+    }
+
+    #[test]
+    fn remove_and_accumulate_lock_info_merges_same_bucket_and_skips_unmatured() {
+        let mut storage = MockStorage::new();
+        let staking_token = b"token";
+        let staker = b"staker";
+
+        insert_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            50,
+            LockInfo {
+                amount: Uint128::new(10),
+                unlock_time: Timestamp::from_seconds(10),
+            },
+        )
+        .unwrap();
+        insert_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            50,
+            LockInfo {
+                amount: Uint128::new(5),
+                unlock_time: Timestamp::from_seconds(40),
+            },
+        )
+        .unwrap();
+        insert_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            50,
+            LockInfo {
+                amount: Uint128::new(7),
+                unlock_time: Timestamp::from_seconds(60),
+            },
+        )
+        .unwrap();
+
+        // Both the 10 and 5 amounts quantize into the era-50 bucket and merge; the era-100
+        // bucket isn't due yet at block_time=50.
+        let swept = remove_and_accumulate_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            Timestamp::from_seconds(50),
+        )
+        .unwrap();
+        assert_eq!(swept, Uint128::new(15));
+
+        let swept_again = remove_and_accumulate_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            Timestamp::from_seconds(50),
+        )
+        .unwrap();
+        assert_eq!(swept_again, Uint128::zero());
+
+        let swept_later = remove_and_accumulate_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            Timestamp::from_seconds(100),
+        )
+        .unwrap();
+        assert_eq!(swept_later, Uint128::new(7));
+    }
+
+    #[test]
+    fn remove_and_accumulate_lock_info_caps_sweep_size() {
+        let mut storage = MockStorage::new();
+        let staking_token = b"token";
+        let staker = b"staker";
+
+        for i in 0..(MAX_BUCKETS_PER_SWEEP + 3) {
+            insert_lock_info(
+                &mut storage,
+                staking_token,
+                staker,
+                1,
+                LockInfo {
+                    amount: Uint128::new(1),
+                    unlock_time: Timestamp::from_seconds(i as u64),
+                },
+            )
+            .unwrap();
+        }
+
+        let first_sweep = remove_and_accumulate_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            Timestamp::from_seconds((MAX_BUCKETS_PER_SWEEP + 3) as u64),
+        )
+        .unwrap();
+        assert_eq!(first_sweep, Uint128::new(MAX_BUCKETS_PER_SWEEP as u128));
+
+        let second_sweep = remove_and_accumulate_lock_info(
+            &mut storage,
+            staking_token,
+            staker,
+            Timestamp::from_seconds((MAX_BUCKETS_PER_SWEEP + 3) as u64),
+        )
+        .unwrap();
+        assert_eq!(second_sweep, Uint128::new(3));
+    }
+}